@@ -0,0 +1,354 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::rc::Rc;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::entry::RgsEntry;
+use crate::error::{Result, RgsError};
+use crate::format::detect_format;
+use crate::header::RgsHeader;
+use crate::manifest::RgsEntryInfo;
+use crate::tables::{
+    read_bytes_vec, read_file_name_entry, read_file_name_table, read_file_type_table,
+    FileNameEntry, FileTypeEntry, FILE_NAME_ENTRY_SIZE, FILE_TYPE_KIND_SND2,
+};
+
+/// A parsed `.rgs` archive, ready to have its entries listed or extracted.
+///
+/// Modeled after `tar::Archive`: [`open`](RgsArchive::open) parses the header
+/// and the file-type/file-name tables once, after which
+/// [`entries`](RgsArchive::entries) hands out lightweight, lazily-read
+/// handles instead of dumping every payload up front.
+pub struct RgsArchive<R> {
+    reader: Rc<RefCell<R>>,
+    file_types: Vec<FileTypeEntry>,
+    file_names: Vec<FileNameEntry>,
+    filetypes_start: u32,
+    name_index: HashMap<String, usize>,
+}
+
+impl<R: Read + Seek> RgsArchive<R> {
+    /// Parses the `RES4` header and both tables out of `r`.
+    pub fn open(mut r: R) -> Result<Self> {
+        r.seek(SeekFrom::End(0))?;
+        let file_size = r.stream_position()?;
+        r.seek(SeekFrom::Start(0))?;
+
+        let header = RgsHeader::read(&mut r, file_size)?;
+
+        r.seek(SeekFrom::Start(header.filetypes_start as u64))?;
+
+        let num_files = r.read_u32::<LittleEndian>()?;
+        // widen to u64 before multiplying: num_files comes straight off disk,
+        // so `num_files * FILE_NAME_ENTRY_SIZE` as a u32 can overflow/wrap
+        // for a malicious file, letting a bogus num_files sail through this
+        // check and blow up the table reads below.
+        let expected = num_files as u64 * FILE_NAME_ENTRY_SIZE as u64;
+        if header.filenames_size as u64 != expected {
+            return Err(RgsError::SizeMismatch {
+                expected,
+                actual: header.filenames_size as u64,
+            });
+        }
+
+        let file_types = read_file_type_table(&mut r, num_files)?;
+        for (index, entry) in file_types.iter().enumerate() {
+            if entry.addr as u64 >= file_size {
+                return Err(RgsError::CorruptEntry {
+                    index,
+                    reason: format!("addr {:#x} is past EOF ({file_size:#x})", entry.addr),
+                });
+            }
+            if entry.kind != FILE_TYPE_KIND_SND2 {
+                return Err(RgsError::BadMagic(entry.kind));
+            }
+        }
+        for (index, pair) in file_types.windows(2).enumerate() {
+            if pair[1].addr <= pair[0].addr {
+                return Err(RgsError::CorruptEntry {
+                    index,
+                    reason: "file-type table addr column is not monotonically increasing".to_string(),
+                });
+            }
+        }
+
+        let filetypes_table_end = r.stream_position()?;
+        if filetypes_table_end != header.filetypes_end as u64 {
+            return Err(RgsError::CorruptEntry {
+                index: file_types.len(),
+                reason: format!(
+                    "file-type table ends at {filetypes_table_end:#x}, header says {:#x}",
+                    header.filetypes_end
+                ),
+            });
+        }
+
+        // each file has a name entry prepended to its data, but there's this
+        // big (redundant) table at the end of the file too
+        let file_names = read_file_name_table(&mut r, num_files)?;
+
+        let name_index = file_names
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.name.clone(), index))
+            .collect();
+
+        Ok(Self {
+            reader: Rc::new(RefCell::new(r)),
+            file_types,
+            file_names,
+            filetypes_start: header.filetypes_start,
+            name_index,
+        })
+    }
+
+    /// Number of files stored in the archive.
+    pub fn len(&self) -> usize {
+        self.file_types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.file_types.is_empty()
+    }
+
+    /// Iterates over every file in the archive without reading any payloads.
+    pub fn entries(&mut self) -> Entries<'_, R> {
+        Entries {
+            archive: &*self,
+            index: 0,
+        }
+    }
+
+    /// Reads a single file's data by its index, without touching any others.
+    pub fn by_index(&mut self, index: usize) -> Result<Option<Vec<u8>>> {
+        if index >= self.file_types.len() {
+            return Ok(None);
+        }
+        Ok(Some(self.entry_at(index).read_data()?))
+    }
+
+    /// Reads a single file's data by name, without dumping the rest of the
+    /// archive. Backed by a name-to-index map built once in [`open`](RgsArchive::open).
+    pub fn get(&mut self, name: &str) -> Result<Option<Vec<u8>>> {
+        match self.name_index.get(name).copied() {
+            Some(index) => self.by_index(index),
+            None => Ok(None),
+        }
+    }
+
+    /// Walks the tables and reports a manifest row per entry, without
+    /// extracting any payloads. Flags zero-byte entries and any disagreement
+    /// between an entry's inline `FileNameEntry` and its redundant copy in
+    /// the trailing table, instead of crashing on them.
+    pub fn list(&mut self) -> Result<Vec<RgsEntryInfo>> {
+        let mut infos = Vec::with_capacity(self.file_types.len());
+
+        for index in 0..self.file_types.len() {
+            let file_type = self.file_types[index];
+            let file_name = self.file_names[index].clone();
+            let data_bound = self.data_bound(index);
+            let data_start = file_type.addr as u64 + FILE_NAME_ENTRY_SIZE as u64;
+            let is_zero_bytes = data_start >= data_bound;
+
+            let mut r = self.reader.borrow_mut();
+            r.seek(SeekFrom::Start(file_type.addr as u64))?;
+            let inline_name_entry = read_file_name_entry(&mut *r)?;
+            let name_entry_mismatch = inline_name_entry != file_name;
+
+            let format = if is_zero_bytes {
+                crate::format::SoundFormat::Unknown
+            } else {
+                let head_len = (file_name.size_in_bytes as u64)
+                    .min(12)
+                    .min(data_bound - data_start) as usize;
+                detect_format(&read_bytes_vec(&mut *r, head_len)?)
+            };
+            drop(r);
+
+            infos.push(RgsEntryInfo {
+                index,
+                name: file_name.name,
+                addr: file_type.addr,
+                size_in_bytes: file_name.size_in_bytes,
+                sample_rate: file_name.sample_rate,
+                bit_depth: file_name.unk5,
+                channel_count: file_name.probably_channel_count,
+                format,
+                is_zero_bytes,
+                name_entry_mismatch,
+            });
+        }
+
+        Ok(infos)
+    }
+
+    /// The absolute file offset `index`'s data must not read past: the next
+    /// entry's `addr`, or the file-type table's start for the last entry.
+    fn data_bound(&self, index: usize) -> u64 {
+        self.file_types
+            .get(index + 1)
+            .map(|next| next.addr)
+            .unwrap_or(self.filetypes_start) as u64
+    }
+
+    fn entry_at(&self, index: usize) -> RgsEntry<R> {
+        let file_type = &self.file_types[index];
+        let file_name = &self.file_names[index];
+        let data_bound = self.data_bound(index);
+
+        RgsEntry {
+            reader: Rc::clone(&self.reader),
+            index,
+            addr: file_type.addr,
+            name: file_name.name.clone(),
+            size_in_bytes: file_name.size_in_bytes,
+            unk2: file_name.unk2,
+            sample_rate: file_name.sample_rate,
+            bit_depth: file_name.unk5,
+            channel_count: file_name.probably_channel_count,
+            data_bound,
+        }
+    }
+}
+
+/// Lazy iterator over an archive's entries, yielded by [`RgsArchive::entries`].
+pub struct Entries<'a, R> {
+    archive: &'a RgsArchive<R>,
+    index: usize,
+}
+
+impl<'a, R: Read + Seek> Iterator for Entries<'a, R> {
+    type Item = Result<RgsEntry<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.archive.file_types.len() {
+            return None;
+        }
+        let entry = self.archive.entry_at(self.index);
+        self.index += 1;
+        Some(Ok(entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::WriteBytesExt;
+    use std::io::Cursor;
+
+    /// A `num_files` this large overflows a plain `u32 * 88` multiplication,
+    /// wrapping (to `0x7FFF_FFA8`) to a value that a malicious
+    /// `filenames_size` could match — this must be caught as `SizeMismatch`,
+    /// not panic or wrap.
+    #[test]
+    fn huge_num_files_does_not_panic_or_wrap() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(crate::header::MAGIC).unwrap();
+        buf.write_u32::<LittleEndian>(16).unwrap(); // filetypes_start
+        buf.write_u32::<LittleEndian>(20).unwrap(); // filetypes_end
+        buf.write_u32::<LittleEndian>(0).unwrap(); // filenames_size
+        buf.write_u32::<LittleEndian>(0x0FFF_FFFF).unwrap(); // num_files, at filetypes_start
+
+        match RgsArchive::open(Cursor::new(buf)) {
+            Err(RgsError::SizeMismatch { expected, actual }) => {
+                assert_eq!(expected, 0x0FFF_FFFFu64 * FILE_NAME_ENTRY_SIZE as u64);
+                assert_eq!(actual, 0);
+            }
+            Err(e) => panic!("expected SizeMismatch, got {e:?}"),
+            Ok(_) => panic!("expected SizeMismatch, archive opened successfully"),
+        }
+    }
+
+    /// A single entry whose `addr` points past EOF must be rejected as
+    /// `CorruptEntry`, not indexed/seeked into blindly.
+    #[test]
+    fn addr_past_eof_is_corrupt_entry() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(crate::header::MAGIC).unwrap();
+        buf.write_u32::<LittleEndian>(16).unwrap(); // filetypes_start
+        buf.write_u32::<LittleEndian>(28).unwrap(); // filetypes_end
+        buf.write_u32::<LittleEndian>(FILE_NAME_ENTRY_SIZE).unwrap(); // filenames_size
+        buf.write_u32::<LittleEndian>(1).unwrap(); // num_files, at filetypes_start
+        buf.write_u32::<LittleEndian>(FILE_TYPE_KIND_SND2).unwrap();
+        buf.write_u32::<LittleEndian>(0x7FFF_FFFF).unwrap(); // addr, well past EOF
+        buf.resize(buf.len() + FILE_NAME_ENTRY_SIZE as usize, 0); // unread name table
+
+        match RgsArchive::open(Cursor::new(buf)) {
+            Err(RgsError::CorruptEntry { index, .. }) => assert_eq!(index, 0),
+            Err(e) => panic!("expected CorruptEntry, got {e:?}"),
+            Ok(_) => panic!("expected CorruptEntry, archive opened successfully"),
+        }
+    }
+
+    /// The file-type table's `addr` column must strictly increase; a file
+    /// whose entries go backwards must be rejected rather than silently
+    /// accepted with bogus `data_bound`s.
+    #[test]
+    fn non_monotonic_addr_is_corrupt_entry() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(crate::header::MAGIC).unwrap();
+        buf.write_u32::<LittleEndian>(16).unwrap(); // filetypes_start
+        buf.write_u32::<LittleEndian>(36).unwrap(); // filetypes_end
+        buf.write_u32::<LittleEndian>(2 * FILE_NAME_ENTRY_SIZE).unwrap(); // filenames_size
+        buf.write_u32::<LittleEndian>(2).unwrap(); // num_files, at filetypes_start
+        buf.write_u32::<LittleEndian>(FILE_TYPE_KIND_SND2).unwrap();
+        buf.write_u32::<LittleEndian>(50).unwrap(); // addr
+        buf.write_u32::<LittleEndian>(FILE_TYPE_KIND_SND2).unwrap();
+        buf.write_u32::<LittleEndian>(30).unwrap(); // addr, goes backwards
+        buf.resize(buf.len() + 2 * FILE_NAME_ENTRY_SIZE as usize, 0); // unread name table
+
+        match RgsArchive::open(Cursor::new(buf)) {
+            Err(RgsError::CorruptEntry { index, .. }) => assert_eq!(index, 0),
+            Err(e) => panic!("expected CorruptEntry, got {e:?}"),
+            Ok(_) => panic!("expected CorruptEntry, archive opened successfully"),
+        }
+    }
+
+    fn build_single_entry_archive(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0u8; 16]; // header, backpatched below
+
+        let addr = buf.len() as u32;
+        let file_name = FileNameEntry {
+            index: 0,
+            name: name.to_string(),
+            unk2: 0,
+            size_in_bytes: data.len() as u32,
+            sample_rate: 44100,
+            unk5: 16,
+            probably_channel_count: 2,
+        };
+        crate::tables::write_file_name_entry(&mut buf, &file_name).unwrap();
+        buf.extend_from_slice(data);
+
+        let filetypes_start = buf.len() as u32;
+        buf.write_u32::<LittleEndian>(1).unwrap();
+        buf.write_u32::<LittleEndian>(FILE_TYPE_KIND_SND2).unwrap();
+        buf.write_u32::<LittleEndian>(addr).unwrap();
+
+        let filetypes_end = buf.len() as u32;
+        crate::tables::write_file_name_entry(&mut buf, &file_name).unwrap();
+
+        buf[0..4].copy_from_slice(&crate::header::MAGIC.to_le_bytes());
+        buf[4..8].copy_from_slice(&filetypes_start.to_le_bytes());
+        buf[8..12].copy_from_slice(&filetypes_end.to_le_bytes());
+        buf[12..16].copy_from_slice(&FILE_NAME_ENTRY_SIZE.to_le_bytes());
+
+        buf
+    }
+
+    #[test]
+    fn get_returns_none_for_a_missing_name() {
+        let buf = build_single_entry_archive("a.wav", b"AAAA");
+        let mut archive = RgsArchive::open(Cursor::new(buf)).unwrap();
+        assert_eq!(archive.get("missing.wav").unwrap(), None);
+    }
+
+    #[test]
+    fn by_index_returns_none_for_an_out_of_range_index() {
+        let buf = build_single_entry_archive("a.wav", b"AAAA");
+        let mut archive = RgsArchive::open(Cursor::new(buf)).unwrap();
+        assert_eq!(archive.by_index(99).unwrap(), None);
+    }
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom};
+use std::rc::Rc;
+
+use crate::error::{Result, RgsError};
+use crate::format::{detect_format, SoundFormat};
+use crate::tables::{read_bytes_vec, read_file_name_entry, FILE_NAME_ENTRY_SIZE};
+
+/// A handle to a single file stored inside an [`RgsArchive`](crate::RgsArchive).
+///
+/// Entries are cheap to hold onto: the payload isn't read off disk until
+/// [`read_data`](RgsEntry::read_data) is called.
+pub struct RgsEntry<R> {
+    pub(crate) reader: Rc<RefCell<R>>,
+    pub(crate) index: usize,
+    pub(crate) addr: u32,
+    pub(crate) name: String,
+    pub(crate) size_in_bytes: u32,
+    pub(crate) unk2: u32,
+    pub(crate) sample_rate: u32,
+    pub(crate) bit_depth: u32,
+    pub(crate) channel_count: u32,
+    /// Absolute file offset this entry's data must not read past (the next
+    /// entry's `addr`, or the file-type table's start for the last entry).
+    pub(crate) data_bound: u64,
+}
+
+impl<R> RgsEntry<R> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size_in_bytes
+    }
+
+    /// The name-table's `unk2` field, seemingly always 0 for wav and 9 for
+    /// mp3 — exposed so callers can cross-check it against [`detect_format`](RgsEntry::detect_format).
+    pub fn unk2(&self) -> u32 {
+        self.unk2
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn bit_depth(&self) -> u32 {
+        self.bit_depth
+    }
+
+    pub fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Sniffs `data`'s actual format from its leading bytes, rather than
+    /// trusting the name-table metadata this entry was built from.
+    pub fn detect_format(&self, data: &[u8]) -> SoundFormat {
+        detect_format(data)
+    }
+}
+
+impl<R: Read + Seek> RgsEntry<R> {
+    /// Seeks to this entry's data, skips the name entry prepended to it, and
+    /// reads exactly `size()` bytes of payload.
+    ///
+    /// `size_in_bytes` comes straight from the archive's own tables, so
+    /// before trusting it (and allocating a `Vec` for it) this checks it
+    /// can't read past the next entry's `addr` or, for the last entry, into
+    /// the file-type table.
+    pub fn read_data(&mut self) -> Result<Vec<u8>> {
+        let data_start = self.addr as u64 + FILE_NAME_ENTRY_SIZE as u64;
+        let data_end = data_start + self.size_in_bytes as u64;
+        if data_end > self.data_bound {
+            return Err(RgsError::CorruptEntry {
+                index: self.index,
+                reason: format!(
+                    "declared size {} bytes would read past the next entry (or into the file-type table)",
+                    self.size_in_bytes
+                ),
+            });
+        }
+
+        let mut r = self.reader.borrow_mut();
+        r.seek(SeekFrom::Start(self.addr as u64))?;
+        let _name_entry = read_file_name_entry(&mut *r)?;
+        read_bytes_vec(&mut *r, self.size_in_bytes as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::RgsArchive;
+    use crate::tables::{write_file_name_entry, FileNameEntry, FILE_TYPE_KIND_SND2};
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    /// Two entries, but the first's table `size_in_bytes` lies and claims
+    /// far more data than the gap before the second entry's `addr` — this
+    /// must surface as `CorruptEntry` rather than reading into (or past)
+    /// the next entry's data.
+    #[test]
+    fn read_data_rejects_size_overrunning_next_entry() {
+        let a_name = FileNameEntry {
+            index: 0,
+            name: "a.wav".to_string(),
+            unk2: 0,
+            size_in_bytes: 1000, // lies: the real gap to "b.wav" is 4 bytes
+            sample_rate: 44100,
+            unk5: 16,
+            probably_channel_count: 2,
+        };
+        let b_name = FileNameEntry {
+            index: 1,
+            name: "b.wav".to_string(),
+            unk2: 0,
+            size_in_bytes: 4,
+            sample_rate: 44100,
+            unk5: 16,
+            probably_channel_count: 2,
+        };
+
+        let mut buf = vec![0u8; 16]; // header, backpatched below
+
+        let addr_a = buf.len() as u32;
+        write_file_name_entry(&mut buf, &a_name).unwrap();
+        buf.extend_from_slice(b"AAAA");
+
+        let addr_b = buf.len() as u32;
+        write_file_name_entry(&mut buf, &b_name).unwrap();
+        buf.extend_from_slice(b"BBBB");
+
+        let filetypes_start = buf.len() as u32;
+        buf.write_u32::<LittleEndian>(2).unwrap(); // num_files
+        buf.write_u32::<LittleEndian>(FILE_TYPE_KIND_SND2).unwrap();
+        buf.write_u32::<LittleEndian>(addr_a).unwrap();
+        buf.write_u32::<LittleEndian>(FILE_TYPE_KIND_SND2).unwrap();
+        buf.write_u32::<LittleEndian>(addr_b).unwrap();
+
+        let filetypes_end = buf.len() as u32;
+        write_file_name_entry(&mut buf, &a_name).unwrap();
+        write_file_name_entry(&mut buf, &b_name).unwrap();
+
+        buf[0..4].copy_from_slice(&crate::header::MAGIC.to_le_bytes());
+        buf[4..8].copy_from_slice(&filetypes_start.to_le_bytes());
+        buf[8..12].copy_from_slice(&filetypes_end.to_le_bytes());
+        buf[12..16].copy_from_slice(&(2 * FILE_NAME_ENTRY_SIZE).to_le_bytes());
+
+        let mut archive = RgsArchive::open(Cursor::new(buf)).unwrap();
+        let mut first = archive.entries().next().unwrap().unwrap();
+        match first.read_data() {
+            Err(RgsError::CorruptEntry { index, .. }) => assert_eq!(index, 0),
+            Err(e) => panic!("expected CorruptEntry, got {e:?}"),
+            Ok(_) => panic!("expected CorruptEntry, read_data succeeded"),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// Errors that can occur while reading or writing an `.rgs` archive.
+#[derive(Debug)]
+pub enum RgsError {
+    Io(std::io::Error),
+    BadMagic(u32),
+    SizeMismatch { expected: u64, actual: u64 },
+    NameTooLong(String),
+    NotAWavFile,
+    UnsupportedExtension(String),
+    CorruptEntry { index: usize, reason: String },
+}
+
+impl fmt::Display for RgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RgsError::Io(e) => write!(f, "i/o error: {e}"),
+            RgsError::BadMagic(magic) => write!(f, "not an RGS archive (bad magic {magic:#010x})"),
+            RgsError::SizeMismatch { expected, actual } => {
+                write!(f, "size mismatch: expected {expected}, got {actual}")
+            }
+            RgsError::NameTooLong(name) => write!(f, "file name too long for a 64-byte slot: {name}"),
+            RgsError::NotAWavFile => write!(f, "not a valid RIFF/WAVE file"),
+            RgsError::UnsupportedExtension(ext) => write!(f, "don't know how to pack files with extension {ext:?}"),
+            RgsError::CorruptEntry { index, reason } => write!(f, "entry {index} is corrupt: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RgsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RgsError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RgsError {
+    fn from(e: std::io::Error) -> Self {
+        RgsError::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, RgsError>;
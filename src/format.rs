@@ -0,0 +1,71 @@
+/// The sniffed audio format of an extracted blob, independent of whatever
+/// the archive's own name-table metadata claims it to be.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SoundFormat {
+    Wav,
+    Mp3,
+    Unknown,
+}
+
+/// Inspects the leading bytes of `data`, the same way `file`/`file-format`
+/// would, rather than trusting the archive's `unk2`/`unk5`/channel-count
+/// heuristics.
+pub fn detect_format(data: &[u8]) -> SoundFormat {
+    if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
+        return SoundFormat::Wav;
+    }
+
+    if data.len() >= 3 && &data[0..3] == b"ID3" {
+        return SoundFormat::Mp3;
+    }
+
+    // MPEG audio frame sync: 11 set bits followed by the layer/version bits.
+    if data.len() >= 2 && data[0] == 0xFF && (data[1] & 0xE0) == 0xE0 {
+        return SoundFormat::Mp3;
+    }
+
+    SoundFormat::Unknown
+}
+
+/// Checks whether an entry's own name-table metadata (`unk2`, `sample_rate`,
+/// bit depth, channel count) is consistent with `format`, based on the
+/// patterns noted in `tables.rs` (wav: `unk2`=0, sample rate/bit depth/
+/// channel count all set; mp3: `unk2`=9, everything else zeroed).
+pub fn metadata_agrees_with_format(
+    format: SoundFormat,
+    unk2: u32,
+    sample_rate: u32,
+    bit_depth: u32,
+    channel_count: u32,
+) -> bool {
+    match format {
+        SoundFormat::Wav => {
+            unk2 == 0
+                && sample_rate != 0
+                && (bit_depth == 8 || bit_depth == 16)
+                && (channel_count == 1 || channel_count == 2)
+        }
+        SoundFormat::Mp3 => unk2 == 9 && sample_rate == 0 && bit_depth == 0 && channel_count == 0,
+        SoundFormat::Unknown => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_format_sniffs_known_magic_bytes() {
+        let cases: [(&str, &[u8], SoundFormat); 6] = [
+            ("RIFF/WAVE header", b"RIFF\0\0\0\0WAVE", SoundFormat::Wav),
+            ("ID3 tag", b"ID3\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00", SoundFormat::Mp3),
+            ("MPEG frame sync", &[0xFF, 0xFB, 0x90, 0x44], SoundFormat::Mp3),
+            ("truncated RIFF header", b"RIFF\0\0\0\0WAV", SoundFormat::Unknown),
+            ("too short to sniff anything", b"RI", SoundFormat::Unknown),
+            ("unrelated bytes", b"garbage bytes here", SoundFormat::Unknown),
+        ];
+        for (label, data, expected) in cases {
+            assert_eq!(detect_format(data), expected, "case: {label}");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::Read;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::error::{Result, RgsError};
+
+pub(crate) const MAGIC: u32 = 0x52455334; // 'RES4'
+
+/// The fixed-size header at the start of every `.rgs` file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RgsHeader {
+    pub filetypes_start: u32,
+    pub filetypes_end: u32,
+    pub filenames_size: u32,
+}
+
+impl RgsHeader {
+    pub fn read<R: Read>(r: &mut R, file_size: u64) -> Result<Self> {
+        let magic = r.read_u32::<LittleEndian>()?;
+        if magic != MAGIC {
+            return Err(RgsError::BadMagic(magic));
+        }
+
+        let filetypes_start = r.read_u32::<LittleEndian>()?;
+        let filetypes_end = r.read_u32::<LittleEndian>()?;
+        let filenames_size = r.read_u32::<LittleEndian>()?;
+
+        let expected = filetypes_end as u64 + filenames_size as u64;
+        if file_size != expected {
+            return Err(RgsError::SizeMismatch {
+                expected,
+                actual: file_size,
+            });
+        }
+
+        Ok(Self {
+            filetypes_start,
+            filetypes_end,
+            filenames_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::WriteBytesExt;
+    use std::io::Cursor;
+
+    /// `filetypes_end + filenames_size` must widen to `u64` before adding —
+    /// a crafted header whose two fields sum past `u32::MAX` overflows a
+    /// plain `u32` add, and in release mode silently wraps (here to
+    /// `0xFFFF_FFFE`) to a value that a crafted header could make equal to
+    /// the real `file_size`, sailing past this check.
+    #[test]
+    fn huge_header_fields_do_not_panic_or_wrap() {
+        let mut buf = Vec::new();
+        buf.write_u32::<LittleEndian>(MAGIC).unwrap();
+        buf.write_u32::<LittleEndian>(0).unwrap(); // filetypes_start
+        buf.write_u32::<LittleEndian>(0xFFFF_FFFF).unwrap(); // filetypes_end
+        buf.write_u32::<LittleEndian>(0xFFFF_FFFF).unwrap(); // filenames_size
+
+        match RgsHeader::read(&mut Cursor::new(buf), 16) {
+            Err(RgsError::SizeMismatch { expected, actual }) => {
+                assert_eq!(expected, 0xFFFF_FFFFu64 + 0xFFFF_FFFFu64);
+                assert_eq!(actual, 16);
+            }
+            Err(e) => panic!("expected SizeMismatch, got {e:?}"),
+            Ok(_) => panic!("expected SizeMismatch, header parsed successfully"),
+        }
+    }
+}
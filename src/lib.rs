@@ -0,0 +1,16 @@
+mod archive;
+mod entry;
+mod error;
+mod format;
+mod header;
+mod manifest;
+mod riff;
+mod tables;
+mod writer;
+
+pub use crate::archive::{Entries, RgsArchive};
+pub use crate::entry::RgsEntry;
+pub use crate::error::{Result, RgsError};
+pub use crate::format::{metadata_agrees_with_format, SoundFormat};
+pub use crate::manifest::{to_csv, to_json, RgsEntryInfo};
+pub use crate::writer::RgsBuilder;
@@ -0,0 +1,148 @@
+use crate::format::SoundFormat;
+
+/// A single row of an archive's manifest, as produced by
+/// [`RgsArchive::list`](crate::RgsArchive::list).
+#[derive(Clone, Debug)]
+pub struct RgsEntryInfo {
+    pub index: usize,
+    pub name: String,
+    pub addr: u32,
+    pub size_in_bytes: u32,
+    pub sample_rate: u32,
+    pub bit_depth: u32,
+    pub channel_count: u32,
+    pub format: SoundFormat,
+    /// The next entry (or the file-type table, for the last one) starts
+    /// immediately after this entry's name header: it stores no payload.
+    pub is_zero_bytes: bool,
+    /// The inline `FileNameEntry` prepended to the data disagrees with the
+    /// redundant copy in the trailing file-name table.
+    pub name_entry_mismatch: bool,
+}
+
+fn escape_json(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Serializes a manifest as a JSON array.
+pub fn to_json(infos: &[RgsEntryInfo]) -> String {
+    let rows: Vec<String> = infos
+        .iter()
+        .map(|info| {
+            format!(
+                "{{\"index\":{},\"name\":\"{}\",\"addr\":{},\"size_in_bytes\":{},\"sample_rate\":{},\"bit_depth\":{},\"channel_count\":{},\"format\":\"{:?}\",\"is_zero_bytes\":{},\"name_entry_mismatch\":{}}}",
+                info.index,
+                escape_json(&info.name),
+                info.addr,
+                info.size_in_bytes,
+                info.sample_rate,
+                info.bit_depth,
+                info.channel_count,
+                info.format,
+                info.is_zero_bytes,
+                info.name_entry_mismatch,
+            )
+        })
+        .collect();
+    format!("[{}]", rows.join(","))
+}
+
+/// Quotes a CSV field per RFC 4180: wraps it in `"..."` and doubles any
+/// embedded quotes. `to_csv` applies it to the name field, always, not just
+/// when the name needs it, since that's simpler and still valid CSV; the
+/// other fields are numbers or enum names and are written unquoted.
+fn escape_csv(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+/// Serializes a manifest as CSV, one row per entry.
+pub fn to_csv(infos: &[RgsEntryInfo]) -> String {
+    let mut out = String::from(
+        "index,name,addr,size_in_bytes,sample_rate,bit_depth,channel_count,format,is_zero_bytes,name_entry_mismatch\n",
+    );
+    for info in infos {
+        out.push_str(&format!(
+            "{},{},{:#x},{},{},{},{},{:?},{},{}\n",
+            info.index,
+            escape_csv(&info.name),
+            info.addr,
+            info.size_in_bytes,
+            info.sample_rate,
+            info.bit_depth,
+            info.channel_count,
+            info.format,
+            info.is_zero_bytes,
+            info.name_entry_mismatch,
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_info(name: &str) -> RgsEntryInfo {
+        RgsEntryInfo {
+            index: 0,
+            name: name.to_string(),
+            addr: 0x100,
+            size_in_bytes: 42,
+            sample_rate: 44100,
+            bit_depth: 16,
+            channel_count: 2,
+            format: SoundFormat::Wav,
+            is_zero_bytes: false,
+            name_entry_mismatch: false,
+        }
+    }
+
+    /// Splits one CSV line into fields, respecting RFC 4180 quoting --
+    /// just enough to check `to_csv`'s output round-trips, not a general
+    /// parser.
+    fn parse_csv_field(line: &str, field_index: usize) -> String {
+        let mut fields = Vec::new();
+        let mut current = String::new();
+        let mut in_quotes = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if in_quotes && chars.peek() == Some(&'"') => {
+                    current.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = !in_quotes,
+                ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+                c => current.push(c),
+            }
+        }
+        fields.push(current);
+        fields[field_index].clone()
+    }
+
+    #[test]
+    fn to_csv_quotes_names_with_commas_and_quotes() {
+        let name = "boss, \"final\".wav";
+        let csv = to_csv(&[sample_info(name)]);
+        let line = csv.lines().nth(1).unwrap();
+
+        assert_eq!(
+            line,
+            format!(
+                "{},{},{:#x},{},{},{},{},{:?},{},{}",
+                0, escape_csv(name), 0x100, 42, 44100, 16, 2, SoundFormat::Wav, false, false,
+            )
+        );
+        assert_eq!(parse_csv_field(line, 1), name);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::error::{Result, RgsError};
+
+/// The handful of `fmt ` chunk fields we need to populate a [`FileNameEntry`](crate::tables::FileNameEntry).
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct WavFmt {
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    pub channel_count: u16,
+}
+
+/// Walks a RIFF/WAVE container's chunks looking for `fmt `, ignoring everything else.
+pub(crate) fn parse_wav_fmt(data: &[u8]) -> Result<WavFmt> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(RgsError::NotAWavFile);
+    }
+
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let chunk_id = &data[pos..pos + 4];
+        let chunk_size = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        let chunk_start = pos + 8;
+
+        if chunk_id == b"fmt " {
+            if chunk_start + 16 > data.len() {
+                return Err(RgsError::NotAWavFile);
+            }
+            let channel_count =
+                u16::from_le_bytes(data[chunk_start + 2..chunk_start + 4].try_into().unwrap());
+            let sample_rate =
+                u32::from_le_bytes(data[chunk_start + 4..chunk_start + 8].try_into().unwrap());
+            let bits_per_sample =
+                u16::from_le_bytes(data[chunk_start + 14..chunk_start + 16].try_into().unwrap());
+            return Ok(WavFmt {
+                sample_rate,
+                bits_per_sample,
+                channel_count,
+            });
+        }
+
+        // chunks are word-aligned
+        pos = chunk_start + chunk_size + (chunk_size & 1);
+    }
+
+    Err(RgsError::NotAWavFile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    /// A chunk ahead of `fmt ` with an odd `chunk_size`, to exercise the
+    /// word-alignment padding byte that `parse_wav_fmt` must skip over.
+    fn wav_with_leading_odd_chunk() -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"RIFF");
+        buf.write_u32::<LittleEndian>(0).unwrap(); // overall size, unchecked by parse_wav_fmt
+        buf.extend_from_slice(b"WAVE");
+
+        buf.extend_from_slice(b"JUNK");
+        buf.write_u32::<LittleEndian>(3).unwrap(); // odd chunk_size
+        buf.extend_from_slice(&[0xAA, 0xBB, 0xCC]);
+        buf.push(0); // pad byte to keep the next chunk word-aligned
+
+        buf.extend_from_slice(b"fmt ");
+        buf.write_u32::<LittleEndian>(16).unwrap();
+        buf.write_u16::<LittleEndian>(1).unwrap(); // wFormatTag: PCM
+        buf.write_u16::<LittleEndian>(2).unwrap(); // channel_count
+        buf.write_u32::<LittleEndian>(44100).unwrap(); // sample_rate
+        buf.write_u32::<LittleEndian>(176_400).unwrap(); // byte rate
+        buf.write_u16::<LittleEndian>(4).unwrap(); // block align
+        buf.write_u16::<LittleEndian>(16).unwrap(); // bits_per_sample
+
+        buf
+    }
+
+    #[test]
+    fn parse_wav_fmt_walks_past_a_leading_odd_sized_chunk() {
+        let fmt = parse_wav_fmt(&wav_with_leading_odd_chunk()).unwrap();
+        assert_eq!(fmt.channel_count, 2);
+        assert_eq!(fmt.sample_rate, 44100);
+        assert_eq!(fmt.bits_per_sample, 16);
+    }
+
+    #[test]
+    fn parse_wav_fmt_rejects_non_riff_data() {
+        assert!(matches!(parse_wav_fmt(b"not a riff file"), Err(RgsError::NotAWavFile)));
+    }
+}
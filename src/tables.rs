@@ -0,0 +1,100 @@
+use std::io::{Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::error::{Result, RgsError};
+
+pub(crate) const FILE_TYPE_KIND_SND2: u32 = 0x534E4432; // 'SND2'
+pub(crate) const FILE_NAME_ENTRY_SIZE: u32 = 88;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct FileTypeEntry {
+    pub kind: u32,
+    pub addr: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct FileNameEntry {
+    pub index: u32,   // incrementing from zero
+    pub name: String, // 64 bytes, presumably ascii
+    pub unk2: u32,    // seemingly always 0 for wav, always 9 for mp3?
+    pub size_in_bytes: u32,
+    pub sample_rate: u32,            // seemingly always 44100 for wav, always 0 for mp3?
+    pub unk5: u32,                   // seemingly always 8 or 16 for wav, always 0 for mp3?
+    pub probably_channel_count: u32, // seemingly always 1 or 2 for wav, always 0 for mp3?
+}
+
+pub(crate) fn read_bytes_arr<const N: usize, R: Read>(r: &mut R) -> Result<[u8; N]> {
+    let mut arr = [0; N];
+    r.read_exact(&mut arr)?;
+    Ok(arr)
+}
+
+pub(crate) fn read_bytes_vec<R: Read>(r: &mut R, len: usize) -> Result<Vec<u8>> {
+    let mut vec = vec![0; len];
+    r.read_exact(&mut vec)?;
+    Ok(vec)
+}
+
+// probably a better way to do this
+pub(crate) fn read_padded_string<const N: usize, R: Read>(r: &mut R) -> Result<String> {
+    let bytes = read_bytes_arr::<N, R>(r)?;
+    let mut s = String::new();
+    for b in bytes {
+        if b != 0 {
+            s.push(b as char);
+        } else {
+            break;
+        }
+    }
+    Ok(s)
+}
+
+pub(crate) fn read_file_type_entry<R: Read>(r: &mut R) -> Result<FileTypeEntry> {
+    Ok(FileTypeEntry {
+        kind: r.read_u32::<LittleEndian>()?,
+        addr: r.read_u32::<LittleEndian>()?,
+    })
+}
+
+pub(crate) fn read_file_type_table<R: Read>(r: &mut R, num_files: u32) -> Result<Vec<FileTypeEntry>> {
+    (0..num_files).map(|_| read_file_type_entry(r)).collect()
+}
+
+pub(crate) fn read_file_name_entry<R: Read>(r: &mut R) -> Result<FileNameEntry> {
+    Ok(FileNameEntry {
+        index: r.read_u32::<LittleEndian>()?,
+        name: read_padded_string::<64, R>(r)?,
+        unk2: r.read_u32::<LittleEndian>()?,
+        size_in_bytes: r.read_u32::<LittleEndian>()?,
+        sample_rate: r.read_u32::<LittleEndian>()?,
+        unk5: r.read_u32::<LittleEndian>()?,
+        probably_channel_count: r.read_u32::<LittleEndian>()?,
+    })
+}
+
+pub(crate) fn read_file_name_table<R: Read>(r: &mut R, num_files: u32) -> Result<Vec<FileNameEntry>> {
+    (0..num_files).map(|_| read_file_name_entry(r)).collect()
+}
+
+pub(crate) fn write_padded_string<const N: usize, W: Write>(w: &mut W, s: &str) -> Result<()> {
+    let bytes = s.as_bytes();
+    if bytes.len() >= N {
+        return Err(RgsError::NameTooLong(s.to_string()));
+    }
+    let mut buf = [0u8; N];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    w.write_all(&buf)?;
+    Ok(())
+}
+
+pub(crate) fn write_file_name_entry<W: Write>(w: &mut W, entry: &FileNameEntry) -> Result<()> {
+    w.write_u32::<LittleEndian>(entry.index)?;
+    write_padded_string::<64, W>(w, &entry.name)?;
+    w.write_u32::<LittleEndian>(entry.unk2)?;
+    w.write_u32::<LittleEndian>(entry.size_in_bytes)?;
+    w.write_u32::<LittleEndian>(entry.sample_rate)?;
+    w.write_u32::<LittleEndian>(entry.unk5)?;
+    w.write_u32::<LittleEndian>(entry.probably_channel_count)?;
+    Ok(())
+}
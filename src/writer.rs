@@ -0,0 +1,183 @@
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::error::{Result, RgsError};
+use crate::header::MAGIC;
+use crate::riff::parse_wav_fmt;
+use crate::tables::{
+    write_file_name_entry, FileNameEntry, FileTypeEntry, FILE_NAME_ENTRY_SIZE,
+    FILE_TYPE_KIND_SND2,
+};
+
+struct SoundMeta {
+    unk2: u32,
+    sample_rate: u32,
+    unk5: u32,
+    channel_count: u32,
+}
+
+impl SoundMeta {
+    fn wav(data: &[u8]) -> Result<Self> {
+        let fmt = parse_wav_fmt(data)?;
+        Ok(Self {
+            unk2: 0,
+            sample_rate: fmt.sample_rate,
+            unk5: fmt.bits_per_sample as u32,
+            channel_count: fmt.channel_count as u32,
+        })
+    }
+
+    fn mp3() -> Self {
+        Self {
+            unk2: 9,
+            sample_rate: 0,
+            unk5: 0,
+            channel_count: 0,
+        }
+    }
+}
+
+/// Builds a fresh `.rgs` archive, mirroring `tar::Builder`: append files one
+/// at a time, then [`finish`](RgsBuilder::finish) writes the trailing tables
+/// and backpatches the header.
+pub struct RgsBuilder<W> {
+    writer: W,
+    file_types: Vec<FileTypeEntry>,
+    file_names: Vec<FileNameEntry>,
+}
+
+impl<W: Write + Seek> RgsBuilder<W> {
+    /// Reserves space for the header, which is backpatched in [`finish`](RgsBuilder::finish).
+    pub fn new(mut writer: W) -> Result<Self> {
+        writer.write_all(&[0u8; 16])?;
+        Ok(Self {
+            writer,
+            file_types: Vec::new(),
+            file_names: Vec::new(),
+        })
+    }
+
+    /// Reads `path` off disk and appends it, inferring wav/mp3 metadata from
+    /// its extension.
+    pub fn append_file(&mut self, path: &Path) -> Result<()> {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let data = std::fs::read(path)?;
+
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let meta = match extension.as_str() {
+            "wav" => SoundMeta::wav(&data)?,
+            "mp3" => SoundMeta::mp3(),
+            other => return Err(RgsError::UnsupportedExtension(other.to_string())),
+        };
+
+        self.append(&name, meta, &data)
+    }
+
+    fn append(&mut self, name: &str, meta: SoundMeta, data: &[u8]) -> Result<()> {
+        let addr = self.writer.stream_position()? as u32;
+        let name_entry = FileNameEntry {
+            index: self.file_types.len() as u32,
+            name: name.to_string(),
+            unk2: meta.unk2,
+            size_in_bytes: data.len() as u32,
+            sample_rate: meta.sample_rate,
+            unk5: meta.unk5,
+            probably_channel_count: meta.channel_count,
+        };
+
+        write_file_name_entry(&mut self.writer, &name_entry)?;
+        self.writer.write_all(data)?;
+
+        self.file_types.push(FileTypeEntry {
+            kind: FILE_TYPE_KIND_SND2,
+            addr,
+        });
+        self.file_names.push(name_entry);
+
+        Ok(())
+    }
+
+    /// Writes the file-type table and the trailing redundant file-name table,
+    /// then seeks back and fills in the header fields, and returns the
+    /// underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        let filetypes_start = self.writer.stream_position()? as u32;
+
+        self.writer
+            .write_u32::<LittleEndian>(self.file_types.len() as u32)?;
+        for entry in &self.file_types {
+            self.writer.write_u32::<LittleEndian>(entry.kind)?;
+            self.writer.write_u32::<LittleEndian>(entry.addr)?;
+        }
+
+        let filetypes_end = self.writer.stream_position()? as u32;
+
+        for entry in &self.file_names {
+            write_file_name_entry(&mut self.writer, entry)?;
+        }
+
+        let filenames_size = self.file_names.len() as u32 * FILE_NAME_ENTRY_SIZE;
+
+        self.writer.seek(SeekFrom::Start(0))?;
+        self.writer.write_u32::<LittleEndian>(MAGIC)?;
+        self.writer.write_u32::<LittleEndian>(filetypes_start)?;
+        self.writer.write_u32::<LittleEndian>(filetypes_end)?;
+        self.writer.write_u32::<LittleEndian>(filenames_size)?;
+
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::RgsArchive;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trips_through_archive() {
+        let mut builder = RgsBuilder::new(Cursor::new(Vec::new())).unwrap();
+        builder
+            .append(
+                "a.wav",
+                SoundMeta {
+                    unk2: 0,
+                    sample_rate: 44100,
+                    unk5: 16,
+                    channel_count: 2,
+                },
+                b"fake wav payload",
+            )
+            .unwrap();
+        builder
+            .append("b.mp3", SoundMeta::mp3(), b"fake mp3 payload")
+            .unwrap();
+        let buf = builder.finish().unwrap().into_inner();
+
+        let mut archive = RgsArchive::open(Cursor::new(buf)).unwrap();
+        assert_eq!(archive.len(), 2);
+
+        let a = archive.get("a.wav").unwrap().unwrap();
+        assert_eq!(a, b"fake wav payload");
+        let b = archive.get("b.mp3").unwrap().unwrap();
+        assert_eq!(b, b"fake mp3 payload");
+
+        let infos = archive.list().unwrap();
+        assert_eq!(infos[0].name, "a.wav");
+        assert_eq!(infos[0].sample_rate, 44100);
+        assert_eq!(infos[0].bit_depth, 16);
+        assert_eq!(infos[0].channel_count, 2);
+        assert!(!infos[0].name_entry_mismatch);
+        assert_eq!(infos[1].name, "b.mp3");
+        assert_eq!(infos[1].sample_rate, 0);
+        assert!(!infos[1].name_entry_mismatch);
+    }
+}